@@ -0,0 +1,162 @@
+//! A library to list WiFi hotspots in your area, using the system's native
+//! tools (`netsh` on Windows, `airport` on macOS) and `tokio` to drive the
+//! underlying process asynchronously. Windows and macOS are supported;
+//! there is no Linux backend yet.
+//!
+//! Enable the `serde` feature to derive `Serialize`/`Deserialize` on
+//! [`Wifi`] and [`Security`], e.g. for shipping scan results over IPC or an
+//! HTTP endpoint.
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod stream;
+mod sys;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub use stream::{scan_stream, ScanDelta};
+#[cfg(target_os = "windows")]
+pub use sys::{connect, disconnect};
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub use sys::scan;
+
+use std::fmt;
+
+/// A single WiFi access point, as reported by the platform's scanning tool.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wifi {
+    /// The access point's MAC address, e.g. `ab:cd:ef:01:23:45`.
+    pub mac: String,
+    /// The network name.
+    pub ssid: String,
+    /// The channel this access point is broadcasting on.
+    pub channel: u16,
+    /// Signal strength in dBm (negative, closer to zero is stronger).
+    pub signal_level: i32,
+    /// The security/authentication scheme in use.
+    pub security: Security,
+    /// The 802.11 radio type (e.g. `802.11ac`), if reported.
+    pub radio_type: Option<String>,
+    /// The frequency band this access point is broadcasting on, if it could
+    /// be determined.
+    pub band: Option<Band>,
+}
+
+/// The frequency band a WiFi access point is operating on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Band {
+    Ghz2_4,
+    Ghz5,
+    Ghz6,
+}
+
+impl Band {
+    /// Guesses the band from a channel number, for backends that don't
+    /// report the band explicitly. Channels 1-14 are 2.4 GHz and 36-165 are
+    /// 5 GHz; 6 GHz channels overlap the 2.4 GHz numbering, so they're only
+    /// ever returned when the band is reported directly.
+    pub fn from_channel(channel: u16) -> Option<Band> {
+        match channel {
+            1..=14 => Some(Band::Ghz2_4),
+            36..=165 => Some(Band::Ghz5),
+            _ => None,
+        }
+    }
+
+    /// Parses a band string like `"2.4 GHz"`, `"5 GHz"` or `"6 GHz"` by its
+    /// leading frequency token, returning `None` for anything else rather
+    /// than guessing.
+    pub fn parse(raw: &str) -> Option<Band> {
+        match raw.split_whitespace().next()? {
+            "2.4" => Some(Band::Ghz2_4),
+            "5" => Some(Band::Ghz5),
+            "6" => Some(Band::Ghz6),
+            _ => None,
+        }
+    }
+}
+
+/// The security/authentication scheme an access point advertises.
+///
+/// Unrecognized schemes are preserved verbatim in [`Security::Other`] rather
+/// than discarded, since platform tools occasionally report vendor-specific
+/// strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Security {
+    Open,
+    Wep,
+    WpaPersonal,
+    Wpa2Personal,
+    Wpa3Personal,
+    Wpa2Enterprise,
+    Other(String),
+}
+
+impl From<&str> for Security {
+    fn from(raw: &str) -> Self {
+        match raw.trim() {
+            "Open" => Security::Open,
+            "WEP" => Security::Wep,
+            "WPA-Personal" => Security::WpaPersonal,
+            "WPA2-Personal" => Security::Wpa2Personal,
+            "WPA3-Personal" => Security::Wpa3Personal,
+            "WPA2-Enterprise" => Security::Wpa2Enterprise,
+            other => Security::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Security {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Security::Open => write!(f, "Open"),
+            Security::Wep => write!(f, "WEP"),
+            Security::WpaPersonal => write!(f, "WPA-Personal"),
+            Security::Wpa2Personal => write!(f, "WPA2-Personal"),
+            Security::Wpa3Personal => write!(f, "WPA3-Personal"),
+            Security::Wpa2Enterprise => write!(f, "WPA2-Enterprise"),
+            Security::Other(raw) => write!(f, "{}", raw),
+        }
+    }
+}
+
+/// Errors that can occur while scanning for, connecting to or disconnecting
+/// from WiFi networks.
+#[derive(Debug)]
+pub enum Error {
+    /// The platform's command-line tool (`netsh` or `airport`) could not be
+    /// found or executed.
+    CommandNotFound,
+    /// One of the internal parsing regexes failed to compile.
+    SyntaxRegexError,
+    /// The scanning command ran but produced no parseable output.
+    NoValueData,
+    /// Adding the generated WLAN profile failed.
+    ProfileAddFailed,
+    /// `netsh wlan connect` itself reported failure (e.g. unknown profile).
+    ConnectFailed,
+    /// The adapter did not associate with the target network in time.
+    AssociationTimeout,
+    /// `netsh wlan disconnect` reported failure.
+    DisconnectFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CommandNotFound => write!(f, "command not found"),
+            Error::SyntaxRegexError => write!(f, "syntax/regex error"),
+            Error::NoValueData => write!(f, "no value data"),
+            Error::ProfileAddFailed => write!(f, "failed to add WLAN profile"),
+            Error::ConnectFailed => write!(f, "netsh wlan connect failed"),
+            Error::AssociationTimeout => write!(f, "timed out waiting to associate"),
+            Error::DisconnectFailed => write!(f, "netsh wlan disconnect failed"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A `Result` alias using this crate's [`Error`] type.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,89 @@
+//! A polling [`Stream`] built on top of [`scan`] that reports what changed
+//! between ticks instead of the raw snapshot.
+
+use std::time::Duration;
+
+use futures::Stream;
+use tokio::time::{interval, Interval};
+
+use crate::{scan, Result, Wifi};
+
+/// The difference between two consecutive [`scan`] results, keyed by
+/// `(mac, ssid)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanDelta {
+    /// Access points present in the latest scan but not the previous one.
+    pub appeared: Vec<Wifi>,
+    /// Access points present in the previous scan but not the latest one.
+    pub disappeared: Vec<Wifi>,
+    /// Access points present in both scans whose signal/channel changed,
+    /// as `(previous, current)` pairs.
+    pub changed: Vec<(Wifi, Wifi)>,
+}
+
+fn key(wifi: &Wifi) -> (&str, &str) {
+    (wifi.mac.as_str(), wifi.ssid.as_str())
+}
+
+fn diff(previous: &[Wifi], current: &[Wifi]) -> ScanDelta {
+    let mut appeared = Vec::new();
+    let mut disappeared = Vec::new();
+    let mut changed = Vec::new();
+
+    for current_wifi in current {
+        match previous.iter().find(|w| key(w) == key(current_wifi)) {
+            Some(previous_wifi) => {
+                if previous_wifi.channel != current_wifi.channel
+                    || previous_wifi.signal_level != current_wifi.signal_level
+                {
+                    changed.push((previous_wifi.clone(), current_wifi.clone()));
+                }
+            }
+            None => appeared.push(current_wifi.clone()),
+        }
+    }
+
+    for previous_wifi in previous {
+        if !current.iter().any(|w| key(w) == key(previous_wifi)) {
+            disappeared.push(previous_wifi.clone());
+        }
+    }
+
+    ScanDelta {
+        appeared,
+        disappeared,
+        changed,
+    }
+}
+
+struct ScanStreamState {
+    interval: Interval,
+    previous: Option<Vec<Wifi>>,
+}
+
+/// Polls [`scan`] every `period` and yields a [`ScanDelta`] for each
+/// successful scan after the first, describing what changed since the
+/// previous successful scan. The first successful scan only establishes the
+/// baseline and yields nothing; a failed scan yields its error and leaves
+/// the baseline as it was.
+pub fn scan_stream(period: Duration) -> impl Stream<Item = Result<ScanDelta>> {
+    let state = ScanStreamState {
+        interval: interval(period),
+        previous: None,
+    };
+
+    futures::stream::unfold(state, |mut state| async move {
+        loop {
+            state.interval.tick().await;
+
+            let current = match scan().await {
+                Ok(current) => current,
+                Err(err) => return Some((Err(err), state)),
+            };
+
+            if let Some(previous) = state.previous.replace(current.clone()) {
+                return Some((Ok(diff(&previous, &current)), state));
+            }
+        }
+    })
+}
@@ -0,0 +1,116 @@
+use crate::{Band, Error, Result, Security, Wifi};
+use regex::Regex;
+use serde::Deserialize;
+use tokio::process::Command;
+
+const AIRPORT_PATH: &str =
+    "/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport";
+
+/// Returns a list of WiFi hotspots in your area - (macOS) uses `airport`.
+///
+/// Prefers the structured `--xml` output, since the column-aligned text
+/// table is fragile to parse; falls back to the text parser if the XML
+/// invocation fails for any reason (e.g. an `airport` build that predates
+/// the flag).
+pub async fn scan() -> Result<Vec<Wifi>> {
+    match scan_xml().await {
+        Ok(wifis) => Ok(wifis),
+        Err(_) => scan_text().await,
+    }
+}
+
+async fn scan_xml() -> Result<Vec<Wifi>> {
+    let output = Command::new(AIRPORT_PATH)
+        .args(&["-s", "--xml"])
+        .output()
+        .await
+        .map_err(|_| Error::CommandNotFound)?;
+
+    if !output.status.success() {
+        return Err(Error::CommandNotFound);
+    }
+
+    let networks: Vec<AirportNetwork> =
+        plist::from_bytes(&output.stdout).map_err(|_| Error::NoValueData)?;
+
+    Ok(networks.into_iter().map(Wifi::from).collect())
+}
+
+async fn scan_text() -> Result<Vec<Wifi>> {
+    let output = Command::new(AIRPORT_PATH)
+        .arg("-s")
+        .output()
+        .await
+        .map_err(|_| Error::CommandNotFound)?;
+
+    let data = String::from_utf8_lossy(&output.stdout);
+    parse_airport_text(&data)
+}
+
+fn parse_airport_text(network_list: &str) -> Result<Vec<Wifi>> {
+    let mut wifis = Vec::new();
+
+    let line_regex = Regex::new(
+        r"(?x)
+        ^\s*(?P<ssid>.*?)\s+
+        (?P<mac>[a-fA-F0-9:]{17})\s+
+        (?P<rssi>-\d+)\s+
+        (?P<channel>\d+)",
+    )
+    .map_err(|_| Error::SyntaxRegexError)?;
+
+    for line in network_list.lines().skip(1) {
+        if let Some(captures) = line_regex.captures(line) {
+            let channel = captures["channel"]
+                .parse::<u16>()
+                .map_err(|_| Error::SyntaxRegexError)?;
+
+            wifis.push(Wifi {
+                mac: captures["mac"].to_string(),
+                ssid: captures["ssid"].to_string(),
+                channel,
+                signal_level: captures["rssi"]
+                    .parse::<i32>()
+                    .map_err(|_| Error::SyntaxRegexError)?,
+                security: Security::Other("unknown".to_string()),
+                radio_type: None,
+                band: Band::from_channel(channel),
+            });
+        }
+    }
+
+    Ok(wifis)
+}
+
+/// A single access point entry from `airport -s --xml`'s plist output.
+///
+/// `serde`/`plist` are hard dependencies of this backend (plist parsing
+/// has no non-serde path), independent of the crate's optional `serde`
+/// feature, which only controls whether the public `Wifi`/`Security`
+/// types derive `Serialize`/`Deserialize`.
+#[derive(Debug, Deserialize)]
+struct AirportNetwork {
+    #[serde(rename = "SSID_STR")]
+    ssid: String,
+    #[serde(rename = "BSSID")]
+    bssid: String,
+    #[serde(rename = "CHANNEL")]
+    channel: u8,
+    #[serde(rename = "RSSI")]
+    rssi: i8,
+}
+
+impl From<AirportNetwork> for Wifi {
+    fn from(network: AirportNetwork) -> Self {
+        let channel = network.channel as u16;
+        Wifi {
+            mac: network.bssid,
+            ssid: network.ssid,
+            channel,
+            signal_level: network.rssi as i32,
+            security: Security::Other("unknown".to_string()),
+            radio_type: None,
+            band: Band::from_channel(channel),
+        }
+    }
+}
@@ -1,7 +1,16 @@
-use crate::{Error, Result, Wifi};
+use crate::{Band, Error, Result, Security, Wifi};
+use itertools::izip;
 use regex::Regex;
+use std::time::Duration;
 use std::vec::Vec;
 use tokio::process::Command;
+use tokio::time::{sleep, Instant};
+
+/// How long to wait for the adapter to associate after `netsh wlan
+/// connect` initiates the connection (that command returns as soon as
+/// association starts, not once it completes).
+const ASSOCIATION_TIMEOUT: Duration = Duration::from_secs(15);
+const ASSOCIATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Returns a list of WiFi hotspots in your area - (Windows) uses `netsh`
 pub async fn scan() -> Result<Vec<Wifi>> {
@@ -15,6 +24,170 @@ pub async fn scan() -> Result<Vec<Wifi>> {
     parse_netsh(&data)
 }
 
+/// Connects to the named network, adding a WLAN profile for it first.
+///
+/// `password` is required for anything but an open network; it's used as
+/// the WPA2-Personal passphrase. `netsh wlan connect` only initiates
+/// association, so this then polls interface state until it reports
+/// `connected`, returning [`Error::AssociationTimeout`] if that hasn't
+/// happened once `ASSOCIATION_TIMEOUT` has elapsed.
+pub async fn connect(ssid: &str, password: Option<&str>) -> Result<()> {
+    let profile_xml = wlan_profile_xml(ssid, password);
+
+    let mut profile_path = std::env::temp_dir();
+    profile_path.push(format!("{}.xml", ssid));
+    tokio::fs::write(&profile_path, profile_xml)
+        .await
+        .map_err(|_| Error::ProfileAddFailed)?;
+
+    let add_result = Command::new("netsh.exe")
+        .args(&[
+            "wlan",
+            "add",
+            "profile",
+            &format!("filename={}", profile_path.display()),
+        ])
+        .output()
+        .await
+        .map_err(|_| Error::CommandNotFound);
+
+    // The profile XML carries the passphrase in cleartext; don't leave it
+    // lying around regardless of how the add went.
+    let _ = tokio::fs::remove_file(&profile_path).await;
+
+    if !add_result?.status.success() {
+        return Err(Error::ProfileAddFailed);
+    }
+
+    let connect_output = Command::new("netsh.exe")
+        .args(&["wlan", "connect", &format!("name={}", ssid)])
+        .output()
+        .await
+        .map_err(|_| Error::CommandNotFound)?;
+
+    if !connect_output.status.success() {
+        return Err(Error::ConnectFailed);
+    }
+
+    wait_for_association(ASSOCIATION_TIMEOUT).await
+}
+
+/// Disconnects from the currently associated network, if any.
+pub async fn disconnect() -> Result<()> {
+    let output = Command::new("netsh.exe")
+        .args(&["wlan", "disconnect"])
+        .output()
+        .await
+        .map_err(|_| Error::CommandNotFound)?;
+
+    if !output.status.success() {
+        return Err(Error::DisconnectFailed);
+    }
+
+    Ok(())
+}
+
+/// Polls `netsh wlan show interfaces` until the adapter reports a
+/// `connected` state, or returns [`Error::AssociationTimeout`] once
+/// `timeout` has elapsed.
+async fn wait_for_association(timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let output = Command::new("netsh.exe")
+            .args(&["wlan", "show", "interfaces"])
+            .output()
+            .await
+            .map_err(|_| Error::CommandNotFound)?;
+
+        let data = String::from_utf8_lossy(&output.stdout);
+        let connected = data.lines().any(|line| {
+            line.contains("State") && line.split(":").nth(1).map(str::trim) == Some("connected")
+        });
+
+        if connected {
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            return Err(Error::AssociationTimeout);
+        }
+
+        sleep(ASSOCIATION_POLL_INTERVAL).await;
+    }
+}
+
+/// Builds the WLAN profile XML netsh expects for `add profile`, choosing
+/// open or WPA2-Personal authentication based on whether a password was
+/// given.
+fn wlan_profile_xml(ssid: &str, password: Option<&str>) -> String {
+    let ssid = escape_xml(ssid);
+
+    let security = match password {
+        Some(_) => {
+            r#"<authEncryption>
+                    <authentication>WPA2PSK</authentication>
+                    <encryption>AES</encryption>
+                    <useOneX>false</useOneX>
+                </authEncryption>"#
+        }
+        None => {
+            r#"<authEncryption>
+                    <authentication>open</authentication>
+                    <encryption>none</encryption>
+                    <useOneX>false</useOneX>
+                </authEncryption>"#
+        }
+    };
+
+    let shared_key = match password {
+        Some(key) => format!(
+            r#"<sharedKey>
+                    <keyType>passPhrase</keyType>
+                    <protected>false</protected>
+                    <keyMaterial>{}</keyMaterial>
+                </sharedKey>"#,
+            escape_xml(key)
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        r#"<?xml version="1.0"?>
+<WLANProfile xmlns="http://www.microsoft.com/networking/WLAN/profile/v1">
+    <name>{ssid}</name>
+    <SSIDConfig>
+        <SSID>
+            <name>{ssid}</name>
+        </SSID>
+    </SSIDConfig>
+    <connectionType>ESS</connectionType>
+    <connectionMode>manual</connectionMode>
+    <MSM>
+        <security>
+            {security}
+            {shared_key}
+        </security>
+    </MSM>
+</WLANProfile>"#,
+        ssid = ssid,
+        security = security,
+        shared_key = shared_key,
+    )
+}
+
+/// Escapes the characters that are significant in XML text/attribute
+/// content, so an SSID or passphrase containing `& < > " '` doesn't
+/// produce malformed profile XML.
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
 fn parse_netsh(network_list: &str) -> Result<Vec<Wifi>> {
     let mut wifis = Vec::new();
 
@@ -29,6 +202,11 @@ fn parse_netsh(network_list: &str) -> Result<Vec<Wifi>> {
         let mut wifi_channels = Vec::new();
         let mut wifi_rssi = Vec::new();
         let mut wifi_security = String::new();
+        let mut wifi_radio_types = Vec::new();
+        let mut wifi_bands = Vec::new();
+
+        let mut radio_type = None;
+        let mut band = None;
 
         for line in block.lines() {
             if let Some(ssid_match) = ssid_regex.find(line) {
@@ -39,8 +217,12 @@ fn parse_netsh(network_list: &str) -> Result<Vec<Wifi>> {
                     .unwrap_or("")
                     .trim()
                     .to_string();
-            } else if let Some(auth_line) = line.split(":").next() {
-                wifi_security = auth_line.trim().to_string();
+            } else if line.contains("Radio type") {
+                radio_type = line.split(":").nth(1).map(|s| s.trim().to_string());
+            } else if line.contains("Band") {
+                band = line.split(":").nth(1).and_then(|s| Band::parse(s.trim()));
+            } else if line.contains("Authentication") {
+                wifi_security = line.split(":").nth(1).unwrap_or("").trim().to_string();
             } else if line.contains("BSSID") {
                 if let Some(captures) = mac_regex.captures(line) {
                     // Default to an empty string if no match is found
@@ -60,16 +242,27 @@ fn parse_netsh(network_list: &str) -> Result<Vec<Wifi>> {
                 if let Some(channel) = line.split(":").nth(1) {
                     wifi_channels.push(channel.trim().to_string());
                 }
+                wifi_radio_types.push(radio_type.take());
+                wifi_bands.push(band.take());
             }
         }
 
-        for (mac, channel, rssi) in izip!(wifi_macs, wifi_channels, wifi_rssi) {
+        for (mac, channel, rssi, radio_type, band) in izip!(
+            wifi_macs,
+            wifi_channels,
+            wifi_rssi,
+            wifi_radio_types,
+            wifi_bands
+        ) {
+            let channel = channel.trim().parse::<u16>().unwrap_or(0);
             wifis.push(Wifi {
                 mac: mac.as_str().to_string(),
                 ssid: wifi_ssid.to_string(),
-                channel: channel.to_string(),
-                signal_level: rssi.to_string(),
-                security: wifi_security.to_string(),
+                channel,
+                signal_level: rssi,
+                security: Security::from(wifi_security.as_str()),
+                radio_type,
+                band: band.or_else(|| Band::from_channel(channel)),
             });
         }
     }
@@ -89,30 +282,38 @@ mod tests {
             Wifi {
                 mac: "ab:cd:ef:01:23:45".to_string(),
                 ssid: "Vodafone Hotspot".to_string(),
-                channel: "6".to_string(),
-                signal_level: "-92".to_string(),
-                security: "Open".to_string(),
+                channel: 6,
+                signal_level: -92,
+                security: Security::Open,
+                radio_type: Some("802.11n".to_string()),
+                band: Some(Band::Ghz2_4),
             },
             Wifi {
                 mac: "ab:cd:ef:01:23:45".to_string(),
                 ssid: "Vodafone Hotspot".to_string(),
-                channel: "6".to_string(),
-                signal_level: "-73".to_string(),
-                security: "Open".to_string(),
+                channel: 6,
+                signal_level: -73,
+                security: Security::Open,
+                radio_type: Some("802.11n".to_string()),
+                band: Some(Band::Ghz2_4),
             },
             Wifi {
                 mac: "ab:cd:ef:01:23:45".to_string(),
                 ssid: "EdaBox".to_string(),
-                channel: "11".to_string(),
-                signal_level: "-82".to_string(),
-                security: "WPA2-Personal".to_string(),
+                channel: 11,
+                signal_level: -82,
+                security: Security::Wpa2Personal,
+                radio_type: Some("802.11ac".to_string()),
+                band: Some(Band::Ghz2_4),
             },
             Wifi {
                 mac: "ab:cd:ef:01:23:45".to_string(),
                 ssid: "FRITZ!Box 2345 Cable".to_string(),
-                channel: "1".to_string(),
-                signal_level: "-50".to_string(),
-                security: "WPA2-Personal".to_string(),
+                channel: 1,
+                signal_level: -50,
+                security: Security::Wpa2Personal,
+                radio_type: Some("802.11ac".to_string()),
+                band: Some(Band::Ghz2_4),
             },
         ];
 